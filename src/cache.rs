@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// A cached `target/` scan result: its byte size, the recursive max mtime
+/// (used for `--older-than`), and `target/`'s own mtime, which is what we
+/// actually compare against to decide whether the entry is still fresh.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub size: u64,
+    pub modified: SystemTime,
+    pub target_modified: SystemTime,
+}
+
+/// Persistent, per-manifest cache of `target/` scan results, so repeated
+/// invocations over the same tree don't re-walk directories that haven't
+/// changed since the last run.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl Cache {
+    fn file() -> Result<PathBuf> {
+        let dirs = ProjectDirs::from("", "", "cargo-vanish")
+            .context("failed to determine cache directory for this platform")?;
+        Ok(dirs.cache_dir().join("scan-cache.json"))
+    }
+
+    /// Load the cache from disk, falling back to an empty one if it doesn't
+    /// exist yet or fails to parse (e.g. from an incompatible older version).
+    pub fn load() -> Result<Cache> {
+        let file = Self::file()?;
+        match fs::read_to_string(&file) {
+            Ok(contents) => Ok(serde_json::from_str(&contents).unwrap_or_default()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Cache::default()),
+            Err(e) => Err(e).with_context(|| format!("failed to read cache at {file:?}")),
+        }
+    }
+
+    /// Overwrite the on-disk cache with exactly these entries. Callers
+    /// should build the `Cache` fresh from the current run's scan rather
+    /// than mutating a loaded one, so manifests that no longer exist are
+    /// dropped instead of lingering forever.
+    pub fn save(&self) -> Result<()> {
+        let file = Self::file()?;
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create cache directory {parent:?}"))?;
+        }
+        let contents = serde_json::to_string(self).context("failed to serialize scan cache")?;
+        fs::write(&file, contents).with_context(|| format!("failed to write cache at {file:?}"))
+    }
+
+    /// Delete the on-disk cache, if present.
+    pub fn clear() -> Result<()> {
+        let file = Self::file()?;
+        match fs::remove_file(&file) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e).with_context(|| format!("failed to remove cache at {file:?}")),
+        }
+    }
+
+    pub fn get(&self, manifest: &Path) -> Option<&CacheEntry> {
+        self.entries.get(manifest)
+    }
+
+    pub fn insert(&mut self, manifest: PathBuf, entry: CacheEntry) {
+        self.entries.insert(manifest, entry);
+    }
+}
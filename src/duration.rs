@@ -0,0 +1,29 @@
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+
+/// Parse a human-friendly duration like `30d`, `2w`, or `6h` into a
+/// [`Duration`]. Accepts an integer amount followed by one of the unit
+/// suffixes `s`/`m`/`h`/`d`/`w` (seconds, minutes, hours, days, weeks).
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let input = input.trim();
+
+    let split_at = input
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| anyhow::anyhow!("missing unit suffix (s/m/h/d/w) in duration {input:?}"))?;
+    let (amount, unit) = input.split_at(split_at);
+
+    let amount: u64 = amount
+        .parse()
+        .with_context(|| format!("failed to parse amount in duration {input:?}"))?;
+
+    let secs = match unit {
+        "s" => amount,
+        "m" => amount * 60,
+        "h" => amount * 60 * 60,
+        "d" => amount * 60 * 60 * 24,
+        "w" => amount * 60 * 60 * 24 * 7,
+        other => bail!("unknown duration unit {other:?} in {input:?}, expected one of s/m/h/d/w"),
+    };
+
+    Ok(Duration::from_secs(secs))
+}
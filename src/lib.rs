@@ -2,13 +2,16 @@ use anyhow::Result;
 use consts::{BLUE, ERASE, GREEN, RED, YELLOW};
 use std::{
     io::{self, Write},
+    sync::atomic::{AtomicBool, Ordering},
     thread,
     time::Duration,
 };
-use walkdir::DirEntry;
 
+pub mod cache;
 pub mod consts;
+pub mod duration;
 pub mod project;
+pub mod size;
 
 #[macro_export]
 macro_rules! color {
@@ -42,11 +45,21 @@ macro_rules! output {
     };
 }
 
+/// Set from `--binary` at startup; switches `to_memory_string` between
+/// 1000-based (SI) and 1024-based (IEC) units.
+pub static BINARY_UNITS: AtomicBool = AtomicBool::new(false);
+
 pub fn to_memory_string(bytes: Option<u64>) -> String {
+    let (base, units) = if BINARY_UNITS.load(Ordering::Relaxed) {
+        (1024u64, ["GiB", "MiB", "KiB"])
+    } else {
+        (1_000u64, ["GB", "MB", "KB"])
+    };
+
     match bytes {
-        Some(bytes) if bytes >= 1_000_000_000 => color!(RED, "{:3} GB", bytes / 1_000_000_000),
-        Some(bytes) if bytes >= 1_000_000 => color!(BLUE, "{:3} MB", bytes / 1_000_000),
-        Some(bytes) if bytes >= 1_000 => color!(GREEN, "{:3} KB", bytes / 1_000),
+        Some(bytes) if bytes >= base.pow(3) => color!(RED, "{:3} {}", bytes / base.pow(3), units[0]),
+        Some(bytes) if bytes >= base.pow(2) => color!(BLUE, "{:3} {}", bytes / base.pow(2), units[1]),
+        Some(bytes) if bytes >= base => color!(GREEN, "{:3} {}", bytes / base, units[2]),
         Some(bytes) =>
         // One extra space between the letters and B because the other units
         // have G/M/B
@@ -57,12 +70,30 @@ pub fn to_memory_string(bytes: Option<u64>) -> String {
     }
 }
 
-pub fn is_hidden(entry: &DirEntry) -> bool {
-    entry
-        .file_name()
-        .to_str()
-        .map(|s| s.starts_with("."))
-        .unwrap_or(false)
+/// Render how long ago `modified` was, in the same units `duration::parse_duration`
+/// accepts, e.g. `3d ago`. `None` (no `target/` to have a mtime) renders like
+/// the `N/A` case in `to_memory_string`.
+pub fn to_age_string(modified: Option<std::time::SystemTime>) -> String {
+    let Some(modified) = modified else {
+        return color!(YELLOW, "N/A");
+    };
+
+    let Ok(elapsed) = modified.elapsed() else {
+        return "just now".to_string();
+    };
+
+    let secs = elapsed.as_secs();
+    if secs >= 60 * 60 * 24 * 7 {
+        format!("{}w ago", secs / (60 * 60 * 24 * 7))
+    } else if secs >= 60 * 60 * 24 {
+        format!("{}d ago", secs / (60 * 60 * 24))
+    } else if secs >= 60 * 60 {
+        format!("{}h ago", secs / (60 * 60))
+    } else if secs >= 60 {
+        format!("{}m ago", secs / 60)
+    } else {
+        format!("{secs}s ago")
+    }
 }
 
 pub fn wait(millis: u64) {
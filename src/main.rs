@@ -3,28 +3,54 @@
 use std::{
     collections::BTreeSet,
     io,
-    path::Path,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::{Duration, SystemTime},
 };
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use cargo_vanish::{
+    cache::Cache,
     consts::{ERASE, GREEN, RESET},
-    erase, is_hidden, output, print,
+    duration::parse_duration,
+    erase, output, print,
     project::Project,
-    to_memory_string, wait,
+    size::parse_size,
+    to_memory_string, wait, BINARY_UNITS,
 };
 use clap::Parser;
+use ignore::{WalkBuilder, WalkState};
 use log::warn;
 use regex::Regex;
 use serde::Serialize;
-use walkdir::WalkDir;
 
 fn main() -> Result<()> {
     env_logger::init();
     let args = Cli::parse();
+    BINARY_UNITS.store(args.binary, Ordering::Relaxed);
 
-    let projs = Projects::new(&args.directory, &args)?;
+    if args.clear_cache {
+        Cache::clear()?;
+        output!("Cleared scan cache.");
+        return Ok(());
+    }
+
+    if args.watch {
+        return watch(&args);
+    }
+
+    let mut cache = load_cache(&args)?;
+    let projs = Projects::new(&args.directory, &args, &mut cache)?;
+    if !args.no_cache {
+        if let Err(e) = cache.save() {
+            warn!("failed to save scan cache: {e}");
+        }
+    }
     if args.list {
         projs.list(&args)
     } else {
@@ -33,6 +59,54 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+fn load_cache(config: &Cli) -> Result<Cache> {
+    if config.no_cache {
+        Ok(Cache::default())
+    } else {
+        Cache::load()
+    }
+}
+
+/// Keep rescanning `config.directory` on an interval and auto-clean whatever
+/// the existing filters (`--exclude`/`--invert`/`--older-than`/`--min-size`)
+/// would include, instead of asking for confirmation once like the one-shot
+/// path.
+fn watch(config: &Cli) -> Result<()> {
+    // `--watch` cleans without a confirmation prompt, so without a gating
+    // filter the bare, obvious invocation would `cargo clean` every project
+    // under `config.directory` every cycle -- including ones being actively
+    // built. Require the caller to opt into which targets actually count as
+    // idle/oversized before letting it run unattended.
+    if config.older_than.is_none() && config.min_size.is_none() {
+        bail!(
+            "--watch requires --older-than and/or --min-size to decide which idle/oversized \
+             projects it's allowed to auto-clean"
+        );
+    }
+
+    let interval = Duration::from_secs(config.interval);
+    loop {
+        let mut cache = load_cache(config)?;
+        let projs = Projects::new(&config.directory, config, &mut cache)?;
+        if !config.no_cache {
+            if let Err(e) = cache.save() {
+                warn!("failed to save scan cache: {e}");
+            }
+        }
+        let matched = projs.included.len();
+        let reclaimed = projs.included.clean_capped(config.max_reclaim, config.quiet);
+
+        if !config.quiet {
+            output!(
+                "Watch cycle: reclaimed {} across {matched} matching projects",
+                to_memory_string(Some(reclaimed)).trim()
+            );
+        }
+
+        thread::sleep(interval);
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct Projects {
     included: BTreeSet<Project>,
@@ -41,7 +115,7 @@ struct Projects {
 
 impl Projects {
     // TODO: make this just take the config?
-    pub fn new(path: impl AsRef<Path>, config: &Cli) -> Result<Projects> {
+    pub fn new(path: impl AsRef<Path>, config: &Cli, cache: &mut Cache) -> Result<Projects> {
         // TODO: do the bar
         let re = if let Some(re) = &config.exclude {
             Regex::new(&re).unwrap()
@@ -50,67 +124,177 @@ impl Projects {
             Regex::new(r"\b\B").unwrap()
         };
 
-        let mut matches = BTreeSet::new();
-        let mut unmatched = BTreeSet::new();
+        let show_progress = atty::is(atty::Stream::Stdout) && !config.json;
 
-        if atty::is(atty::Stream::Stdout) && !config.json {
+        if show_progress {
             // Extra newline gets eaten by first erase
             output!("Searching for projects:\n");
         }
 
-        for entry in WalkDir::new(path)
-            .into_iter()
-            .filter_entry(|e| !is_hidden(e) || config.hidden)
-            .filter_map(|e| match e {
-                Ok(e) => Some(e),
-                Err(e) => {
-                    warn!("WalkDir error: {e}");
-                    None
+        // Directories matched by `.gitignore`/`.ignore`/global excludes are
+        // pruned before we ever descend into them, so a `Cargo.toml` nested
+        // inside one (vendored dependencies, `/target` itself, etc) won't be
+        // found unless `--no-ignore` is passed to disable that pruning
+        // entirely. There's no way to whitelist just `Cargo.toml` here: a
+        // glob override only matches the file itself, not the ignored
+        // ancestor directories the walker never even opens.
+        let mut builder = WalkBuilder::new(&path);
+        builder
+            .hidden(!config.hidden)
+            .git_ignore(!config.no_ignore)
+            .git_exclude(!config.no_ignore)
+            .ignore(!config.no_ignore)
+            .threads(config.threads);
+
+        let (tx, rx) = mpsc::channel();
+        builder.build_parallel().run(|| {
+            let tx = tx.clone();
+            Box::new(move |entry| {
+                match entry {
+                    Ok(entry) if entry.file_name() == "Cargo.toml" => {
+                        let _ = tx.send(entry);
+                    }
+                    Ok(_) => (),
+                    Err(e) => warn!("ignore walk error: {e}"),
                 }
+                WalkState::Continue
             })
-            .filter(|d| d.file_name().to_str() == Some("Cargo.toml"))
-        {
-            let project = Project::new(entry.path().to_owned()).unwrap();
-
-            if atty::is(atty::Stream::Stdout) && !config.json {
-                // Erase before so that project remains displayed until next
-                // one is ready
-                print(ERASE);
-                output!(
-                    "{}",
-                    project // "{} {}",
-                            // to_memory_string(project.size),
-                            // project.path.parent().unwrap().to_str().unwrap()
-                );
+        });
+        drop(tx);
+
+        // `.hidden(!config.hidden)` above already prunes dotfiles/dotdirs
+        // during the walk, so nothing further to filter here.
+        let manifests: Vec<PathBuf> = rx.into_iter().map(|e| e.path().to_owned()).collect();
+
+        if show_progress {
+            // Erase "Searching for projects"
+            print(ERASE);
+        }
+
+        // Computing each project's size means walking its `target/` dir,
+        // which dominates startup time for large monorepos. Farm that out
+        // across a worker pool instead of folding over one `WalkDir` at a
+        // time, and have a printer thread repaint a live progress line off
+        // shared atomics instead of faking progress with sleeps.
+        let total = manifests.len();
+        let queue = Arc::new(Mutex::new(manifests.into_iter()));
+        let scanned = Arc::new(AtomicUsize::new(0));
+        let bytes_scanned = Arc::new(AtomicU64::new(0));
+        let done = Arc::new(AtomicBool::new(false));
+        let (proj_tx, proj_rx) = mpsc::channel();
+
+        let printer = show_progress.then(|| {
+            let scanned = Arc::clone(&scanned);
+            let bytes_scanned = Arc::clone(&bytes_scanned);
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    print(ERASE);
+                    output!(
+                        "scanned {}/{total} projects, {}",
+                        scanned.load(Ordering::Relaxed),
+                        to_memory_string(Some(bytes_scanned.load(Ordering::Relaxed))).trim()
+                    );
+                    wait(100);
+                }
+            })
+        });
+
+        let workers = match config.threads {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+
+        // Only read from the cache here; it's rebuilt from scratch below
+        // once every project has actually been scanned, so a manifest that
+        // disappeared since the last run doesn't linger in it forever.
+        let read_cache: &Cache = cache;
+        thread::scope(|scope| {
+            for _ in 0..workers.max(1) {
+                let queue = Arc::clone(&queue);
+                let scanned = Arc::clone(&scanned);
+                let bytes_scanned = Arc::clone(&bytes_scanned);
+                let proj_tx = proj_tx.clone();
+                scope.spawn(move || loop {
+                    let Some(manifest) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    match Project::new(manifest, Some(read_cache)) {
+                        Ok(project) => {
+                            scanned.fetch_add(1, Ordering::Relaxed);
+                            bytes_scanned.fetch_add(project.size().unwrap_or(0), Ordering::Relaxed);
+                            let _ = proj_tx.send(project);
+                        }
+                        Err(e) => warn!("failed to load project: {e}"),
+                    }
+                });
             }
+        });
+        drop(proj_tx);
+
+        done.store(true, Ordering::Relaxed);
+        if let Some(printer) = printer {
+            let _ = printer.join();
+        }
 
+        if show_progress {
+            // Final erase for the progress line
+            print(ERASE);
+        }
+
+        // Collecting into a `BTreeSet` sorts by `Project`'s `Ord` regardless
+        // of the (nondeterministic) order workers finish in, so the final
+        // split below is deterministic even though the scan above isn't.
+        let mut matches = BTreeSet::new();
+        let mut unmatched = BTreeSet::new();
+        *cache = Cache::default();
+        for project in proj_rx {
+            if let Some(entry) = project.cache_entry() {
+                cache.insert(project.path().to_owned(), entry);
+            }
             if re.find(project.path().to_str().unwrap()).is_some() {
                 matches.insert(project);
             } else {
                 unmatched.insert(project);
             }
-
-            wait(15);
-        }
-
-        if atty::is(atty::Stream::Stdout) && !config.json {
-            // Final erase for last item
-            print(ERASE);
-            // Erase "searching for projects"
-            print(ERASE);
         }
 
-        if config.invert {
-            Ok(Projects {
+        let mut projects = if config.invert {
+            Projects {
                 included: matches,
                 ignored: unmatched,
-            })
+            }
         } else {
-            Ok(Projects {
+            Projects {
                 included: unmatched,
                 ignored: matches,
-            })
+            }
+        };
+
+        if let Some(older_than) = config.older_than {
+            let cutoff = SystemTime::now() - older_than;
+            // A project with no `modified` time has no `target/` to clean,
+            // so it can never be "older than" a cutoff.
+            projects
+                .included
+                .retain(|p| p.modified().is_some_and(|m| m <= cutoff));
         }
+
+        // A project with no `target/` has nothing to reclaim, so it counts
+        // as zero bytes here: excluded by any non-zero `--min-size`, kept by
+        // any `--max-size`.
+        if let Some(min_size) = config.min_size {
+            projects
+                .included
+                .retain(|p| p.size().unwrap_or(0) >= min_size);
+        }
+        if let Some(max_size) = config.max_size {
+            projects
+                .included
+                .retain(|p| p.size().unwrap_or(0) <= max_size);
+        }
+
+        Ok(projects)
     }
 
     pub fn list(&self, config: &Cli) {
@@ -186,6 +370,41 @@ struct Cli {
     #[arg(short = 'H', long)]
     hidden: bool,
 
+    /// Don't respect .gitignore, .ignore, and other ignore files when
+    /// searching for projects. This is currently the only way to discover a
+    /// Cargo.toml that lives under an ignored directory (e.g. a vendored
+    /// dependency under a gitignored `vendor/`) — the `ignore` crate has no
+    /// public knob to un-ignore just the ancestor directories of a matched
+    /// file while leaving the rest of its pruning intact, so selective
+    /// re-inclusion of manifests is descoped in favor of this all-or-nothing
+    /// flag
+    #[arg(long)]
+    no_ignore: bool,
+
+    /// Number of threads to use for project discovery. 0 lets the ignore
+    /// crate pick
+    #[arg(long, default_value_t = 0)]
+    threads: usize,
+
+    /// Only include projects whose target/ hasn't been modified within this
+    /// long, e.g. `30d`, `2w`, `6h`
+    #[arg(long, value_parser = parse_duration)]
+    older_than: Option<Duration>,
+
+    /// Only include projects whose target/ is at least this large, e.g.
+    /// `500MB`, `2G`, `750KiB`
+    #[arg(long, value_parser = parse_size)]
+    min_size: Option<u64>,
+
+    /// Only include projects whose target/ is at most this large
+    #[arg(long, value_parser = parse_size)]
+    max_size: Option<u64>,
+
+    /// Render sizes with 1024-based (KiB/MiB/GiB) units instead of the
+    /// default 1000-based (KB/MB/GB) units
+    #[arg(long)]
+    binary: bool,
+
     /// Don't ask for confirmation when cleaning directories
     #[arg(short, long)]
     yes: bool,
@@ -197,11 +416,42 @@ struct Cli {
     /// List projects which were ignored
     #[arg(short, long, requires = "exclude")]
     ignored: bool,
+
+    /// Keep running, periodically re-scanning and auto-cleaning idle
+    /// projects instead of exiting after one pass
+    #[arg(long)]
+    watch: bool,
+
+    /// Seconds between watch-mode scans
+    #[arg(long, default_value_t = 300, requires = "watch")]
+    interval: u64,
+
+    /// Suppress per-project output while in watch mode
+    #[arg(long, requires = "watch")]
+    quiet: bool,
+
+    /// Stop cleaning further projects in a watch cycle once this many bytes
+    /// have been reclaimed. The project that crosses the cap is still cleaned
+    /// in full, so a cycle can reclaim more than this; unset means no cap
+    #[arg(long, requires = "watch")]
+    max_reclaim: Option<u64>,
+
+    /// Don't read or write the persistent scan cache
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Delete the persistent scan cache and exit
+    #[arg(long)]
+    clear_cache: bool,
 }
 
 trait Vanish {
     fn list(&self);
     fn clean(&self);
+    /// Like `clean`, but skips the confirmation prompt, stops once `cap`
+    /// bytes have been reclaimed (if set), and optionally suppresses
+    /// per-project output. Returns the number of bytes actually reclaimed.
+    fn clean_capped(&self, cap: Option<u64>, quiet: bool) -> u64;
 }
 
 impl Vanish for BTreeSet<Project> {
@@ -242,4 +492,37 @@ impl Vanish for BTreeSet<Project> {
             }
         }
     }
+
+    fn clean_capped(&self, cap: Option<u64>, quiet: bool) -> u64 {
+        let mut reclaimed = 0;
+        // `Project`'s `Ord` sorts by size ascending, so reverse the
+        // iteration: when a cap is set we want it spent on the biggest
+        // offenders first, not exhausted on the smallest ones.
+        for project in self.iter().rev() {
+            if cap.is_some_and(|cap| reclaimed >= cap) {
+                break;
+            }
+
+            if !quiet {
+                output!("Cleaning: {:?}", project.path());
+            }
+            match Command::new("cargo")
+                .arg("clean")
+                .arg("--manifest-path")
+                .arg(project.path())
+                .stdout(Stdio::inherit())
+                .status()
+            {
+                Ok(status) if status.success() => reclaimed += project.size().unwrap_or(0),
+                Ok(status) => warn!("Error cleaning {:?}: cargo clean exited with {status}", project.path()),
+                Err(e) => warn!("Error cleaning {:?}: {e}", project.path()),
+            }
+            if !quiet {
+                if let Err(e) = erase() {
+                    warn!("Error clearning screen: {e}")
+                }
+            }
+        }
+        reclaimed
+    }
 }
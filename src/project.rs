@@ -3,11 +3,14 @@ use serde::Serialize;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use toml::{Table, Value};
 use walkdir::WalkDir;
 
-use crate::to_memory_string;
+use crate::cache::{Cache, CacheEntry};
+use crate::{to_age_string, to_memory_string};
 
 /// A project is uniquely identified by the path to its Cargo.toml. Note that
 /// the path stored in self.0 includes the `Cargo.toml` at the end.
@@ -16,6 +19,11 @@ pub struct Project {
     path: PathBuf,
     name: Name,
     size: Option<u64>,
+    /// Most recent modification time seen while walking `target/`, or `None`
+    /// if there's no `target/` to have one.
+    modified: Option<SystemTime>,
+    /// `target/`'s own mtime, used only as the scan cache's freshness key.
+    target_modified: Option<SystemTime>,
 }
 
 /// Name of a project. `Explicit` corresponds to a name in the package.name field
@@ -37,7 +45,11 @@ impl Display for Name {
 }
 
 impl Project {
-    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+    /// Build a `Project` for the manifest at `path`. If `cache` holds an
+    /// entry for `path` whose `target_modified` still matches `target/`'s
+    /// current mtime, its cached size/modified are reused instead of
+    /// re-walking `target/`.
+    pub fn new(path: impl AsRef<Path>, cache: Option<&Cache>) -> Result<Self> {
         let path = path.as_ref();
         // Make sure it's a valid Cargo.toml
         match path.file_name() {
@@ -73,18 +85,64 @@ impl Project {
                     .to_string(),
             ));
 
-        // Get the size
         let mut initial = Project {
             path: path.to_owned(),
             name,
             size: None,
+            modified: None,
+            target_modified: None,
         };
-        initial.size = initial.dirsize()?;
+
+        let mut target = initial.path.parent().unwrap().to_owned();
+        target.push("target/");
+
+        let target_modified = match fs::metadata(&target) {
+            Ok(meta) => Some(meta.modified().unwrap_or(SystemTime::UNIX_EPOCH)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(anyhow::Error::from(e)).context("failed to access target directory")
+            }
+        };
+
+        if let Some(target_modified) = target_modified {
+            let cached = cache
+                .and_then(|cache| cache.get(&initial.path))
+                .filter(|entry| entry.target_modified == target_modified);
+
+            let sized = match cached {
+                Some(entry) => Some((entry.size, entry.modified)),
+                // Cache miss (or disabled): fall back to the full walk.
+                // `target/` may have been removed since the metadata check
+                // above (TOCTOU under `--watch` churn or a concurrent
+                // `cargo clean`) -- treat that the same as "no target"
+                // rather than asserting it must still be there.
+                None => initial.dirsize()?,
+            };
+
+            if let Some((size, modified)) = sized {
+                initial.size = Some(size);
+                initial.modified = Some(modified);
+                initial.target_modified = Some(target_modified);
+            }
+        }
 
         return Ok(initial);
     }
 
-    pub fn dirsize(&self) -> Result<Option<u64>> {
+    /// The `CacheEntry` a caller should persist for this project, or `None`
+    /// if there's no `target/` worth caching.
+    pub fn cache_entry(&self) -> Option<CacheEntry> {
+        Some(CacheEntry {
+            size: (*self.size())?,
+            modified: (*self.modified())?,
+            target_modified: (*self.target_modified())?,
+        })
+    }
+
+    /// Sum the byte length of every file under `target/`, alongside the most
+    /// recent modification time seen. Returns `None` if there's no `target/`
+    /// directory at all.
+    pub fn dirsize(&self) -> Result<Option<(u64, SystemTime)>> {
         // Get path to target/ dir
         let mut target = self.path.parent().unwrap().to_owned();
         target.push("target/");
@@ -98,12 +156,21 @@ impl Project {
             }
         }
 
-        Ok(Some(
-            WalkDir::new(target)
-                .into_iter()
-                .filter_map(|e| e.ok())
-                .fold(0, |acc, item| acc + item.metadata().unwrap().len()),
-        ))
+        let (size, modified) = WalkDir::new(target)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .fold((0, SystemTime::UNIX_EPOCH), |(size, latest), item| {
+                // A file can disappear between being listed and being
+                // stat'd (a concurrent `cargo build`/`clean`, or `--watch`
+                // churn); just skip it rather than panicking the scan.
+                let Ok(metadata) = item.metadata() else {
+                    return (size, latest);
+                };
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                (size + metadata.len(), latest.max(modified))
+            });
+
+        Ok(Some((size, modified)))
     }
 
     pub fn path(&self) -> &Path {
@@ -113,6 +180,14 @@ impl Project {
     pub fn size(&self) -> &Option<u64> {
         &self.size
     }
+
+    pub fn modified(&self) -> &Option<SystemTime> {
+        &self.modified
+    }
+
+    pub fn target_modified(&self) -> &Option<SystemTime> {
+        &self.target_modified
+    }
 }
 
 impl Debug for Project {
@@ -121,6 +196,8 @@ impl Debug for Project {
             .field("path", &self.path)
             .field("name", &self.name as &dyn Debug)
             .field("size", &self.size as &dyn Debug)
+            .field("modified", &self.modified as &dyn Debug)
+            .field("target_modified", &self.target_modified as &dyn Debug)
             .finish()
     }
 }
@@ -129,8 +206,9 @@ impl Display for Project {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{} {} @ {:?}",
+            "{} {} {} @ {:?}",
             to_memory_string(self.size),
+            to_age_string(self.modified),
             self.name,
             self.path().parent().unwrap().to_str()
         )
@@ -0,0 +1,35 @@
+use anyhow::{bail, Context, Result};
+
+/// Parse a human-friendly size like `500MB`, `2G`, or `750KiB` into a byte
+/// count. Accepts both SI (decimal, `K`/`KB`/`M`/`MB`/`G`/`GB`/`T`/`TB`) and
+/// IEC (binary, `KiB`/`MiB`/`GiB`/`TiB`) suffixes; a bare number is treated
+/// as bytes.
+pub fn parse_size(input: &str) -> Result<u64> {
+    let input = input.trim();
+
+    let split_at = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (amount, unit) = input.split_at(split_at);
+
+    let amount: u64 = amount
+        .parse()
+        .with_context(|| format!("failed to parse amount in size {input:?}"))?;
+
+    let multiplier: u64 = match unit {
+        "" | "B" => 1,
+        "K" | "KB" => 1_000,
+        "M" | "MB" => 1_000_000,
+        "G" | "GB" => 1_000_000_000,
+        "T" | "TB" => 1_000_000_000_000,
+        "KiB" => 1024,
+        "MiB" => 1024 * 1024,
+        "GiB" => 1024 * 1024 * 1024,
+        "TiB" => 1024 * 1024 * 1024 * 1024,
+        other => bail!(
+            "unknown size unit {other:?} in {input:?}, expected one of B/K/KB/M/MB/G/GB/T/TB/KiB/MiB/GiB/TiB"
+        ),
+    };
+
+    Ok(amount * multiplier)
+}